@@ -0,0 +1,143 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use crate::PriceResponse;
+
+/// How long a cached quote is served before it's considered stale and
+/// re-fetched from upstream.
+pub const CACHE_TTL: Duration = Duration::from_secs(5 * 60);
+
+/// If an upstream quote's own timestamp is older than this, flag the
+/// response as `stale` rather than silently serving it as fresh.
+pub const MAX_QUOTE_AGE_SECS: i64 = 15 * 60;
+
+/// FX rates move far more slowly than quotes, so they get a much longer TTL.
+pub const RATE_TTL: Duration = Duration::from_secs(6 * 60 * 60);
+
+const DEFAULT_YAHOO_BASE_URL: &str = "https://query1.finance.yahoo.com";
+const DEFAULT_ALPHA_VANTAGE_BASE_URL: &str = "https://www.alphavantage.co";
+const DEFAULT_STOOQ_BASE_URL: &str = "https://stooq.com";
+
+type PriceCache = HashMap<(String, String), (PriceResponse, Instant)>;
+type RateCache = HashMap<(String, String), (f64, Instant)>;
+
+#[derive(Clone)]
+pub struct AppState {
+    cache: Arc<Mutex<PriceCache>>,
+    rates: Arc<Mutex<RateCache>>,
+    /// Crypto ticker -> provider id, lazily populated from the listings
+    /// endpoint on first crypto lookup.
+    crypto_ids: Arc<Mutex<HashMap<String, String>>>,
+    /// Shared so the Yahoo, Alpha Vantage and Stooq fetches don't build a new
+    /// `reqwest::Client` (and its own connection pool) on every request. The
+    /// crypto and FX rate lookups still build their own client per call.
+    http_client: reqwest::Client,
+    yahoo_base_url: String,
+    alpha_vantage_base_url: String,
+    stooq_base_url: String,
+}
+
+impl Default for AppState {
+    fn default() -> Self {
+        Self {
+            cache: Arc::default(),
+            rates: Arc::default(),
+            crypto_ids: Arc::default(),
+            http_client: reqwest::Client::new(),
+            yahoo_base_url: DEFAULT_YAHOO_BASE_URL.to_string(),
+            alpha_vantage_base_url: DEFAULT_ALPHA_VANTAGE_BASE_URL.to_string(),
+            stooq_base_url: DEFAULT_STOOQ_BASE_URL.to_string(),
+        }
+    }
+}
+
+impl AppState {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Point at a different Yahoo base URL, e.g. a local mock server in
+    /// tests. Chainable with the other `with_*_base_url` builders so a test
+    /// can redirect several sources on the same `AppState`.
+    pub fn with_yahoo_base_url(mut self, base_url: impl Into<String>) -> Self {
+        self.yahoo_base_url = base_url.into();
+        self
+    }
+
+    /// Point at a different Alpha Vantage base URL, e.g. a local mock server
+    /// in tests.
+    pub fn with_alpha_vantage_base_url(mut self, base_url: impl Into<String>) -> Self {
+        self.alpha_vantage_base_url = base_url.into();
+        self
+    }
+
+    /// Point at a different Stooq base URL, e.g. a local mock server in
+    /// tests.
+    pub fn with_stooq_base_url(mut self, base_url: impl Into<String>) -> Self {
+        self.stooq_base_url = base_url.into();
+        self
+    }
+
+    pub fn http_client(&self) -> &reqwest::Client {
+        &self.http_client
+    }
+
+    pub fn yahoo_base_url(&self) -> &str {
+        &self.yahoo_base_url
+    }
+
+    pub fn alpha_vantage_base_url(&self) -> &str {
+        &self.alpha_vantage_base_url
+    }
+
+    pub fn stooq_base_url(&self) -> &str {
+        &self.stooq_base_url
+    }
+
+    pub fn get(&self, symbol: &str, source: &str) -> Option<PriceResponse> {
+        let cache = self.cache.lock().unwrap();
+        let (price, inserted_at) = cache.get(&(symbol.to_string(), source.to_string()))?;
+        if inserted_at.elapsed() < CACHE_TTL {
+            Some(price.clone())
+        } else {
+            None
+        }
+    }
+
+    pub fn put(&self, symbol: &str, source: &str, price: PriceResponse) {
+        let mut cache = self.cache.lock().unwrap();
+        cache.insert((symbol.to_string(), source.to_string()), (price, Instant::now()));
+    }
+
+    pub fn get_rate(&self, from: &str, to: &str) -> Option<f64> {
+        let rates = self.rates.lock().unwrap();
+        let (rate, inserted_at) = rates.get(&(from.to_string(), to.to_string()))?;
+        if inserted_at.elapsed() < RATE_TTL {
+            Some(*rate)
+        } else {
+            None
+        }
+    }
+
+    pub fn put_rate(&self, from: &str, to: &str, rate: f64) {
+        let mut rates = self.rates.lock().unwrap();
+        rates.insert((from.to_string(), to.to_string()), (rate, Instant::now()));
+    }
+
+    pub fn crypto_id(&self, symbol: &str) -> Option<String> {
+        self.crypto_ids.lock().unwrap().get(symbol).cloned()
+    }
+
+    pub fn set_crypto_ids(&self, ids: HashMap<String, String>) {
+        *self.crypto_ids.lock().unwrap() = ids;
+    }
+}
+
+/// Flag a freshly-fetched quote as `stale` if its own `timestamp` is older
+/// than `MAX_QUOTE_AGE_SECS`, rather than returning it as if it were live.
+pub fn mark_staleness(mut price: PriceResponse) -> PriceResponse {
+    let age = chrono::Utc::now().timestamp() - price.timestamp;
+    price.stale = age > MAX_QUOTE_AGE_SECS;
+    price
+}
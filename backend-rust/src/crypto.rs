@@ -0,0 +1,133 @@
+use crate::cache::AppState;
+use crate::error::FetchError;
+use crate::PriceResponse;
+
+/// Tickers routed to the crypto source by default instead of falling through
+/// to the (bogus, for these symbols) mock/stock price path. Not exhaustive —
+/// anything not in this list still works via `?source=crypto` explicitly.
+const KNOWN_CRYPTO_SYMBOLS: &[&str] = &[
+    "BTC", "ETH", "SOL", "DOGE", "ADA", "XRP", "LTC", "DOT", "MATIC", "AVAX", "BNB", "SHIB",
+];
+
+pub fn is_crypto_symbol(symbol: &str) -> bool {
+    KNOWN_CRYPTO_SYMBOLS.contains(&symbol.to_uppercase().as_str())
+}
+
+/// Canonical CoinGecko id for each symbol in `KNOWN_CRYPTO_SYMBOLS`. The
+/// listings endpoint (`/coins/list`) is ordered alphabetically by id, not by
+/// market cap, so "first match wins" over it frequently lands on an obscure
+/// token sharing the ticker rather than the well-known coin — these are
+/// pinned explicitly instead.
+const CANONICAL_IDS: &[(&str, &str)] = &[
+    ("BTC", "bitcoin"),
+    ("ETH", "ethereum"),
+    ("SOL", "solana"),
+    ("DOGE", "dogecoin"),
+    ("ADA", "cardano"),
+    ("XRP", "ripple"),
+    ("LTC", "litecoin"),
+    ("DOT", "polkadot"),
+    ("MATIC", "matic-network"),
+    ("AVAX", "avalanche-2"),
+    ("BNB", "binancecoin"),
+    ("SHIB", "shiba-inu"),
+];
+
+/// Resolve `symbol` (e.g. `BTC`) against a market-data API and return its
+/// price quoted in `vs` (e.g. `usd`, `eth`, `btc`).
+pub async fn fetch_crypto_price(
+    state: &AppState,
+    symbol: &str,
+    vs: &str,
+) -> Result<PriceResponse, FetchError> {
+    let symbol = symbol.to_uppercase();
+    let vs = vs.to_lowercase();
+
+    let id = resolve_id(state, &symbol).await?;
+
+    let url = format!(
+        "https://api.coingecko.com/api/v3/simple/price?ids={}&vs_currencies={}&include_24hr_change=true",
+        id, vs
+    );
+    let client = reqwest::Client::new();
+    let response = client
+        .get(&url)
+        .send()
+        .await
+        .map_err(|e| FetchError::Transport(e.to_string()))?;
+
+    if response.status() == reqwest::StatusCode::TOO_MANY_REQUESTS {
+        return Err(FetchError::RateLimited);
+    }
+    if !response.status().is_success() {
+        return Err(FetchError::StatusCode(response.status().as_u16()));
+    }
+
+    let data: serde_json::Value = response
+        .json()
+        .await
+        .map_err(|e| FetchError::InvalidResponse(e.to_string()))?;
+
+    let price = data[&id][&vs]
+        .as_f64()
+        .ok_or_else(|| FetchError::InvalidResponse(format!("missing {} price for {}", vs, id)))?;
+    let change_percent_24h = data[&id][format!("{}_24h_change", vs)].as_f64();
+    let change_24h = change_percent_24h.map(|pct| price * pct / 100.0);
+
+    Ok(PriceResponse {
+        symbol,
+        price,
+        currency: vs.to_uppercase(),
+        timestamp: chrono::Utc::now().timestamp(),
+        source: "crypto".to_string(),
+        change_24h,
+        change_percent_24h,
+        agreeing_sources: None,
+        stale: false,
+    })
+}
+
+/// Look up `symbol`'s provider id: a curated id for well-known tickers, or
+/// lazily populating the symbol->id table from CoinGecko's listings endpoint
+/// on first use for anything else.
+async fn resolve_id(state: &AppState, symbol: &str) -> Result<String, FetchError> {
+    if let Some((_, id)) = CANONICAL_IDS.iter().find(|(sym, _)| *sym == symbol) {
+        return Ok(id.to_string());
+    }
+
+    if let Some(id) = state.crypto_id(symbol) {
+        return Ok(id);
+    }
+
+    let client = reqwest::Client::new();
+    let response = client
+        .get("https://api.coingecko.com/api/v3/coins/list")
+        .send()
+        .await
+        .map_err(|e| FetchError::Transport(e.to_string()))?;
+
+    if !response.status().is_success() {
+        return Err(FetchError::StatusCode(response.status().as_u16()));
+    }
+
+    let listings: Vec<serde_json::Value> = response
+        .json()
+        .await
+        .map_err(|e| FetchError::InvalidResponse(e.to_string()))?;
+
+    let mut ids = std::collections::HashMap::new();
+    for entry in &listings {
+        if let (Some(id), Some(sym)) = (entry["id"].as_str(), entry["symbol"].as_str()) {
+            // CoinGecko lists many obscure tokens sharing a ticker with no
+            // reliable way to rank them from this endpoint alone; first
+            // match wins here, but anything in KNOWN_CRYPTO_SYMBOLS already
+            // resolved via CANONICAL_IDS above and never reaches this path.
+            ids.entry(sym.to_uppercase()).or_insert_with(|| id.to_string());
+        }
+    }
+    state.set_crypto_ids(ids);
+
+    state
+        .crypto_id(symbol)
+        .ok_or_else(|| FetchError::SymbolNotFound(symbol.to_string()))
+}
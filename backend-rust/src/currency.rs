@@ -0,0 +1,74 @@
+use crate::cache::AppState;
+use crate::error::FetchError;
+use crate::PriceResponse;
+
+/// ISO-4217 codes this service knows how to convert into. Not exhaustive,
+/// but covers the major currencies callers are likely to ask for.
+const ISO_4217_CODES: &[&str] = &[
+    "USD", "EUR", "GBP", "JPY", "CHF", "CAD", "AUD", "NZD", "CNY", "HKD", "SGD", "INR", "KRW",
+    "MXN", "BRL", "ZAR", "SEK", "NOK", "DKK", "PLN",
+];
+
+fn is_valid_currency(code: &str) -> bool {
+    ISO_4217_CODES.contains(&code)
+}
+
+/// Convert a `PriceResponse`'s `price` and `change_24h` from its native
+/// currency into `target`, replacing `currency` with the target code.
+pub async fn convert(
+    state: &AppState,
+    mut price: PriceResponse,
+    target: &str,
+) -> Result<PriceResponse, FetchError> {
+    let target = target.to_uppercase();
+    if !is_valid_currency(&target) {
+        return Err(FetchError::InvalidCurrency(target));
+    }
+
+    if target == price.currency {
+        return Ok(price);
+    }
+
+    let rate = exchange_rate(state, &price.currency, &target).await?;
+    price.price = (price.price * rate * 100.0).round() / 100.0;
+    price.change_24h = price.change_24h.map(|c| (c * rate * 100.0).round() / 100.0);
+    price.currency = target;
+    Ok(price)
+}
+
+/// Look up the `from` -> `to` exchange rate, serving a cached value when
+/// it's still within `cache::RATE_TTL` and otherwise fetching live from a
+/// free FX rates endpoint.
+async fn exchange_rate(state: &AppState, from: &str, to: &str) -> Result<f64, FetchError> {
+    if from == to {
+        return Ok(1.0);
+    }
+
+    if let Some(rate) = state.get_rate(from, to) {
+        return Ok(rate);
+    }
+
+    let url = format!("https://api.exchangerate.host/latest?base={}&symbols={}", from, to);
+    let client = reqwest::Client::new();
+    let response = client
+        .get(&url)
+        .send()
+        .await
+        .map_err(|e| FetchError::Transport(e.to_string()))?;
+
+    if !response.status().is_success() {
+        return Err(FetchError::StatusCode(response.status().as_u16()));
+    }
+
+    let data: serde_json::Value = response
+        .json()
+        .await
+        .map_err(|e| FetchError::InvalidResponse(e.to_string()))?;
+
+    let rate = data["rates"][to]
+        .as_f64()
+        .ok_or_else(|| FetchError::InvalidResponse(format!("missing rate for {}", to)))?;
+
+    state.put_rate(from, to, rate);
+    Ok(rate)
+}
@@ -0,0 +1,104 @@
+use std::future::Future;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use axum::http::StatusCode;
+use thiserror::Error;
+
+const MAX_ATTEMPTS: u32 = 4;
+const BASE_DELAY: Duration = Duration::from_millis(200);
+
+/// Structured failure modes for the fetch layer, replacing an opaque
+/// `anyhow::Error` so callers can tell "rate limited, retry later" apart
+/// from "symbol not found" instead of always getting a 400.
+#[derive(Debug, Error)]
+pub enum FetchError {
+    #[error("upstream returned HTTP {0}")]
+    StatusCode(u16),
+    #[error("rate limited by upstream")]
+    RateLimited,
+    #[error("invalid response from upstream: {0}")]
+    InvalidResponse(String),
+    #[error("transport error: {0}")]
+    Transport(String),
+    #[error("unsupported source: {0}")]
+    Unsupported(String),
+    #[error("{0}")]
+    QuorumNotMet(String),
+    #[error("invalid currency code: {0}")]
+    InvalidCurrency(String),
+    #[error("symbol not found: {0}")]
+    SymbolNotFound(String),
+}
+
+impl FetchError {
+    /// Only transient failures are worth retrying: rate limiting, 5xx, and
+    /// transport-level errors. 4xx (other than 429) and parse failures are
+    /// treated as permanent.
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            FetchError::RateLimited | FetchError::Transport(_) => true,
+            FetchError::StatusCode(code) => *code >= 500,
+            _ => false,
+        }
+    }
+
+    pub fn status_code(&self) -> StatusCode {
+        match self {
+            FetchError::StatusCode(404) => StatusCode::NOT_FOUND,
+            FetchError::StatusCode(_) => StatusCode::BAD_GATEWAY,
+            FetchError::RateLimited => StatusCode::TOO_MANY_REQUESTS,
+            FetchError::InvalidResponse(_) => StatusCode::BAD_GATEWAY,
+            FetchError::Transport(_) => StatusCode::BAD_GATEWAY,
+            FetchError::Unsupported(_) => StatusCode::BAD_REQUEST,
+            FetchError::QuorumNotMet(_) => StatusCode::SERVICE_UNAVAILABLE,
+            FetchError::InvalidCurrency(_) => StatusCode::BAD_REQUEST,
+            FetchError::SymbolNotFound(_) => StatusCode::NOT_FOUND,
+        }
+    }
+
+    pub fn error_code(&self) -> &'static str {
+        match self {
+            FetchError::StatusCode(404) => "SYMBOL_NOT_FOUND",
+            FetchError::StatusCode(_) => "UPSTREAM_ERROR",
+            FetchError::RateLimited => "RATE_LIMITED",
+            FetchError::InvalidResponse(_) => "INVALID_RESPONSE",
+            FetchError::Transport(_) => "TRANSPORT_ERROR",
+            FetchError::Unsupported(_) => "UNSUPPORTED_SOURCE",
+            FetchError::QuorumNotMet(_) => "QUORUM_NOT_MET",
+            FetchError::InvalidCurrency(_) => "INVALID_CURRENCY",
+            FetchError::SymbolNotFound(_) => "SYMBOL_NOT_FOUND",
+        }
+    }
+}
+
+/// Retry `attempt` on transient failures (429/5xx/transport) with exponential
+/// backoff and jitter, up to `MAX_ATTEMPTS` tries. Non-retryable failures
+/// (other 4xx, parse errors) are returned immediately.
+pub async fn with_retry<F, Fut, T>(mut attempt: F) -> Result<T, FetchError>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T, FetchError>>,
+{
+    let mut last_err = None;
+    for attempt_num in 0..MAX_ATTEMPTS {
+        match attempt().await {
+            Ok(value) => return Ok(value),
+            Err(e) if e.is_retryable() && attempt_num + 1 < MAX_ATTEMPTS => {
+                let backoff = BASE_DELAY * 2u32.pow(attempt_num);
+                tokio::time::sleep(backoff + jitter()).await;
+                last_err = Some(e);
+            }
+            Err(e) => return Err(e),
+        }
+    }
+    Err(last_err.expect("loop always sets last_err before exhausting MAX_ATTEMPTS"))
+}
+
+/// Cheap jitter (0-50ms) to avoid retry storms without pulling in a RNG crate.
+fn jitter() -> Duration {
+    let nanos = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.subsec_nanos())
+        .unwrap_or(0);
+    Duration::from_millis((nanos % 50) as u64)
+}
@@ -0,0 +1,603 @@
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    response::Json,
+    routing::get,
+    Router,
+};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use tower_http::cors::CorsLayer;
+use tracing::{info, warn};
+
+pub mod cache;
+pub mod crypto;
+pub mod currency;
+pub mod error;
+pub mod stream;
+
+pub use cache::AppState;
+pub use error::FetchError;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PriceResponse {
+    pub symbol: String,
+    pub price: f64,
+    pub currency: String,
+    pub timestamp: i64,
+    pub source: String,
+    pub change_24h: Option<f64>,
+    pub change_percent_24h: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub agreeing_sources: Option<Vec<String>>,
+    #[serde(default)]
+    pub stale: bool,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ErrorResponse {
+    pub error: String,
+    pub message: String,
+}
+
+pub fn build_router(state: AppState) -> Router {
+    Router::new()
+        .route("/health", get(health_check))
+        .route("/price/:symbol", get(get_price))
+        .route("/prices", get(get_multiple_prices))
+        .route("/stream", get(stream::stream_handler))
+        .layer(CorsLayer::permissive())
+        .with_state(state)
+}
+
+async fn health_check() -> Json<serde_json::Value> {
+    Json(serde_json::json!({
+        "status": "healthy",
+        "service": "WSB Price Fetcher",
+        "timestamp": chrono::Utc::now().timestamp()
+    }))
+}
+
+async fn get_price(
+    State(state): State<AppState>,
+    Path(symbol): Path<String>,
+    Query(params): Query<HashMap<String, String>>,
+) -> Result<Json<PriceResponse>, (StatusCode, Json<ErrorResponse>)> {
+    let symbol = symbol.to_uppercase();
+    let requested_source = params.get("source").cloned().unwrap_or_else(|| "yahoo".to_string());
+    let no_cache = params.get("no_cache").map(|v| v == "true").unwrap_or(false);
+
+    let convert = params.get("convert");
+    let vs = params.get("vs").cloned().unwrap_or_else(|| "usd".to_string());
+    let seed = params.get("seed").and_then(|v| v.parse::<u64>().ok());
+    let source = resolve_source(&symbol, &requested_source);
+    let cache_key = cache_key_source(&source, &vs);
+
+    if !no_cache {
+        if let Some(cached) = state.get(&symbol, &cache_key) {
+            info!("Serving {} from {} from cache", symbol, source);
+            return convert_response(&state, cached, convert).await;
+        }
+    }
+
+    info!("Fetching price for {} from {}", symbol, source);
+
+    let result = if source.eq_ignore_ascii_case("quorum") {
+        let (min, max_deviation) = quorum_params(&params);
+        fetch_quorum_price(&state, &symbol, min, max_deviation).await
+    } else {
+        fetch_price(&state, &symbol, &source, &vs, seed).await
+    };
+
+    match result {
+        Ok(price_data) => {
+            let price_data = cache::mark_staleness(price_data);
+            info!("Successfully fetched price for {}: ${:.2}", symbol, price_data.price);
+            state.put(&symbol, &cache_key, price_data.clone());
+            convert_response(&state, price_data, convert).await
+        }
+        Err(e) => {
+            warn!("Failed to fetch price for {}: {}", symbol, e);
+            Err((
+                e.status_code(),
+                Json(ErrorResponse {
+                    error: e.error_code().to_string(),
+                    message: format!("Failed to fetch price for {}: {}", symbol, e),
+                }),
+            ))
+        }
+    }
+}
+
+/// Apply `?convert=<currency>` to a price, if requested.
+async fn convert_response(
+    state: &AppState,
+    price: PriceResponse,
+    convert: Option<&String>,
+) -> Result<Json<PriceResponse>, (StatusCode, Json<ErrorResponse>)> {
+    match convert {
+        Some(target) => match currency::convert(state, price, target).await {
+            Ok(converted) => Ok(Json(converted)),
+            Err(e) => Err((
+                e.status_code(),
+                Json(ErrorResponse {
+                    error: e.error_code().to_string(),
+                    message: e.to_string(),
+                }),
+            )),
+        },
+        None => Ok(Json(price)),
+    }
+}
+
+async fn get_multiple_prices(
+    State(state): State<AppState>,
+    Query(params): Query<HashMap<String, String>>,
+) -> Result<Json<Vec<PriceResponse>>, (StatusCode, Json<ErrorResponse>)> {
+    let symbols = params.get("symbols")
+        .ok_or_else(|| (StatusCode::BAD_REQUEST, Json(ErrorResponse {
+            error: "MISSING_SYMBOLS".to_string(),
+            message: "Missing 'symbols' parameter. Use comma-separated values like: ?symbols=AAPL,TSLA,MSFT".to_string(),
+        })))?;
+
+    let symbol_list: Vec<String> = symbols.split(',').map(|s| s.trim().to_uppercase()).collect();
+    let requested_source = params.get("source").cloned().unwrap_or_else(|| "yahoo".to_string());
+    let no_cache = params.get("no_cache").map(|v| v == "true").unwrap_or(false);
+    let vs = params.get("vs").cloned().unwrap_or_else(|| "usd".to_string());
+    let seed = params.get("seed").and_then(|v| v.parse::<u64>().ok());
+
+    info!("Fetching prices for {} symbols from {}", symbol_list.len(), requested_source);
+
+    let mut prices = Vec::new();
+    let mut errors = Vec::new();
+    let (min, max_deviation) = quorum_params(&params);
+    let convert = params.get("convert");
+
+    for symbol in symbol_list {
+        let source = resolve_source(&symbol, &requested_source);
+        let cache_key = cache_key_source(&source, &vs);
+
+        let price_data = if !no_cache {
+            state.get(&symbol, &cache_key)
+        } else {
+            None
+        };
+
+        let price_data = match price_data {
+            Some(cached) => Ok(cached),
+            None => {
+                let result = if source.eq_ignore_ascii_case("quorum") {
+                    fetch_quorum_price(&state, &symbol, min, max_deviation).await
+                } else {
+                    fetch_price(&state, &symbol, &source, &vs, seed).await
+                };
+                result.map(|price_data| {
+                    let price_data = cache::mark_staleness(price_data);
+                    state.put(&symbol, &cache_key, price_data.clone());
+                    price_data
+                })
+            }
+        };
+
+        let price_data = match price_data {
+            Ok(price_data) => price_data,
+            Err(e) => {
+                warn!("Failed to fetch price for {}: {}", symbol, e);
+                errors.push(format!("{}: {}", symbol, e));
+                continue;
+            }
+        };
+
+        match convert {
+            Some(target) => match currency::convert(&state, price_data, target).await {
+                Ok(converted) => prices.push(converted),
+                Err(e) => errors.push(format!("{}: {}", symbol, e)),
+            },
+            None => prices.push(price_data),
+        }
+    }
+
+    if prices.is_empty() {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            Json(ErrorResponse {
+                error: "ALL_PRICES_FAILED".to_string(),
+                message: format!("Failed to fetch any prices. Errors: {}", errors.join(", ")),
+            }),
+        ));
+    }
+
+    Ok(Json(prices))
+}
+
+/// Parse the `min` and `max_deviation` query params used by `source=quorum`.
+fn quorum_params(params: &HashMap<String, String>) -> (usize, f64) {
+    let min = params
+        .get("min")
+        .and_then(|v| v.parse::<usize>().ok())
+        .unwrap_or(2)
+        .max(1);
+    let max_deviation = params
+        .get("max_deviation")
+        .and_then(|v| v.parse::<f64>().ok())
+        .unwrap_or(DEFAULT_MAX_DEVIATION_PCT);
+    (min, max_deviation)
+}
+
+/// Route obvious crypto tickers (`BTC`, `ETH`, ...) to the crypto source
+/// when `source` is `yahoo` (the default), instead of falling through to a
+/// bogus mock/stock quote. An explicit `source=yahoo` is not told apart from
+/// the default and is rerouted the same way.
+pub(crate) fn resolve_source(symbol: &str, source: &str) -> String {
+    if source.eq_ignore_ascii_case("yahoo") && crypto::is_crypto_symbol(symbol) {
+        "crypto".to_string()
+    } else {
+        source.to_string()
+    }
+}
+
+/// Cache key for a source, folding in `vs` for crypto quotes since the same
+/// symbol priced in USD vs. BTC is a different quote entirely.
+fn cache_key_source(source: &str, vs: &str) -> String {
+    if source.eq_ignore_ascii_case("crypto") {
+        format!("crypto:{}", vs.to_lowercase())
+    } else {
+        source.to_string()
+    }
+}
+
+pub async fn fetch_price(
+    state: &AppState,
+    symbol: &str,
+    source: &str,
+    vs: &str,
+    seed: Option<u64>,
+) -> Result<PriceResponse, FetchError> {
+    match source.to_lowercase().as_str() {
+        "yahoo" => fetch_yahoo_price(state, symbol).await,
+        "alpha_vantage" => fetch_alpha_vantage_price(state, symbol).await,
+        "stooq" => fetch_stooq_price(state, symbol).await,
+        "mock" => fetch_mock_price(symbol, seed).await,
+        "crypto" => crypto::fetch_crypto_price(state, symbol, vs).await,
+        other => {
+            warn!("Unknown source: {}", other);
+            Err(FetchError::Unsupported(other.to_string()))
+        }
+    }
+}
+
+async fn fetch_yahoo_price(state: &AppState, symbol: &str) -> Result<PriceResponse, FetchError> {
+    error::with_retry(|| fetch_yahoo_price_once(state, symbol)).await
+}
+
+async fn fetch_yahoo_price_once(state: &AppState, symbol: &str) -> Result<PriceResponse, FetchError> {
+    // Using a free Yahoo Finance API alternative; the client and base URL
+    // come from shared state so tests can point this at a mock server.
+    let url = format!("{}/v8/finance/chart/{}", state.yahoo_base_url(), symbol);
+
+    let response = state
+        .http_client()
+        .get(&url)
+        .header("User-Agent", "WSB-Price-Fetcher/1.0")
+        .send()
+        .await
+        .map_err(|e| FetchError::Transport(e.to_string()))?;
+
+    if response.status() == reqwest::StatusCode::TOO_MANY_REQUESTS {
+        return Err(FetchError::RateLimited);
+    }
+    if !response.status().is_success() {
+        return Err(FetchError::StatusCode(response.status().as_u16()));
+    }
+
+    let data: serde_json::Value = response
+        .json()
+        .await
+        .map_err(|e| FetchError::InvalidResponse(e.to_string()))?;
+
+    let result = data["chart"]["result"][0]
+        .as_object()
+        .ok_or_else(|| FetchError::InvalidResponse("missing chart.result[0]".to_string()))?;
+
+    let meta = result["meta"]
+        .as_object()
+        .ok_or_else(|| FetchError::InvalidResponse("missing chart.result[0].meta".to_string()))?;
+
+    let price = meta["regularMarketPrice"]
+        .as_f64()
+        .ok_or_else(|| FetchError::InvalidResponse("missing regularMarketPrice".to_string()))?;
+
+    let currency = meta["currency"].as_str().unwrap_or("USD");
+    let change_24h = meta["regularMarketChange"].as_f64();
+    let change_percent_24h = meta["regularMarketChangePercent"].as_f64();
+    let timestamp = meta["regularMarketTime"]
+        .as_i64()
+        .unwrap_or_else(|| chrono::Utc::now().timestamp());
+
+    Ok(PriceResponse {
+        symbol: symbol.to_string(),
+        price,
+        currency: currency.to_string(),
+        timestamp,
+        source: "yahoo".to_string(),
+        change_24h,
+        change_percent_24h,
+        agreeing_sources: None,
+        stale: false,
+    })
+}
+
+/// Real second live source for `quorum` (and `?source=alpha_vantage`
+/// directly). Requires `ALPHA_VANTAGE_API_KEY`; without it we return
+/// `Unsupported` rather than silently substituting mock data, since a mock
+/// quote agreeing with itself would make `quorum` meaningless.
+async fn fetch_alpha_vantage_price(state: &AppState, symbol: &str) -> Result<PriceResponse, FetchError> {
+    let api_key = std::env::var("ALPHA_VANTAGE_API_KEY")
+        .map_err(|_| FetchError::Unsupported("alpha_vantage requires ALPHA_VANTAGE_API_KEY".to_string()))?;
+
+    error::with_retry(|| fetch_alpha_vantage_price_once(state, symbol, &api_key)).await
+}
+
+async fn fetch_alpha_vantage_price_once(
+    state: &AppState,
+    symbol: &str,
+    api_key: &str,
+) -> Result<PriceResponse, FetchError> {
+    // The client and base URL come from shared state so tests can point this
+    // at a mock server, same as `fetch_yahoo_price_once`.
+    let url = format!(
+        "{}/query?function=GLOBAL_QUOTE&symbol={}&apikey={}",
+        state.alpha_vantage_base_url(),
+        symbol,
+        api_key
+    );
+
+    let response = state
+        .http_client()
+        .get(&url)
+        .send()
+        .await
+        .map_err(|e| FetchError::Transport(e.to_string()))?;
+
+    if response.status() == reqwest::StatusCode::TOO_MANY_REQUESTS {
+        return Err(FetchError::RateLimited);
+    }
+    if !response.status().is_success() {
+        return Err(FetchError::StatusCode(response.status().as_u16()));
+    }
+
+    let data: serde_json::Value = response
+        .json()
+        .await
+        .map_err(|e| FetchError::InvalidResponse(e.to_string()))?;
+
+    let quote = data["Global Quote"]
+        .as_object()
+        .filter(|q| !q.is_empty())
+        .ok_or_else(|| FetchError::InvalidResponse("missing Global Quote".to_string()))?;
+
+    let price = quote["05. price"]
+        .as_str()
+        .and_then(|s| s.parse::<f64>().ok())
+        .ok_or_else(|| FetchError::InvalidResponse("missing 05. price".to_string()))?;
+
+    let change_24h = quote["09. change"].as_str().and_then(|s| s.parse::<f64>().ok());
+    let change_percent_24h = quote["10. change percent"]
+        .as_str()
+        .and_then(|s| s.trim_end_matches('%').parse::<f64>().ok());
+
+    Ok(PriceResponse {
+        symbol: symbol.to_string(),
+        price,
+        currency: "USD".to_string(),
+        timestamp: chrono::Utc::now().timestamp(),
+        source: "alpha_vantage".to_string(),
+        change_24h,
+        change_percent_24h,
+        agreeing_sources: None,
+        stale: false,
+    })
+}
+
+/// Key-free second live source for `quorum`. Stooq's free quote endpoint
+/// needs no API key, so `yahoo` + `stooq` alone can reach quorum in a
+/// keyless deployment — `alpha_vantage` participates too when
+/// `ALPHA_VANTAGE_API_KEY` happens to be set, but quorum no longer depends
+/// on it.
+async fn fetch_stooq_price(state: &AppState, symbol: &str) -> Result<PriceResponse, FetchError> {
+    error::with_retry(|| fetch_stooq_price_once(state, symbol)).await
+}
+
+async fn fetch_stooq_price_once(state: &AppState, symbol: &str) -> Result<PriceResponse, FetchError> {
+    let url = format!(
+        "{}/q/l/?s={}.us&f=sd2t2ohlcv&h&e=csv",
+        state.stooq_base_url(),
+        symbol.to_lowercase()
+    );
+
+    let response = state
+        .http_client()
+        .get(&url)
+        .send()
+        .await
+        .map_err(|e| FetchError::Transport(e.to_string()))?;
+
+    if response.status() == reqwest::StatusCode::TOO_MANY_REQUESTS {
+        return Err(FetchError::RateLimited);
+    }
+    if !response.status().is_success() {
+        return Err(FetchError::StatusCode(response.status().as_u16()));
+    }
+
+    let body = response
+        .text()
+        .await
+        .map_err(|e| FetchError::Transport(e.to_string()))?;
+
+    let data_line = body
+        .lines()
+        .nth(1)
+        .ok_or_else(|| FetchError::InvalidResponse("missing CSV data row".to_string()))?;
+
+    let fields: Vec<&str> = data_line.split(',').collect();
+    if fields.len() < 7 {
+        return Err(FetchError::InvalidResponse(format!("malformed CSV row: {}", data_line)));
+    }
+    let (date, time, close) = (fields[1], fields[2], fields[6]);
+
+    if close.eq_ignore_ascii_case("N/D") {
+        return Err(FetchError::SymbolNotFound(symbol.to_string()));
+    }
+
+    let price = close
+        .parse::<f64>()
+        .map_err(|_| FetchError::InvalidResponse(format!("invalid close price: {}", close)))?;
+
+    let timestamp = chrono::NaiveDateTime::parse_from_str(&format!("{} {}", date, time), "%Y-%m-%d %H:%M:%S")
+        .map(|dt| dt.and_utc().timestamp())
+        .unwrap_or_else(|_| chrono::Utc::now().timestamp());
+
+    Ok(PriceResponse {
+        symbol: symbol.to_string(),
+        price,
+        currency: "USD".to_string(),
+        timestamp,
+        source: "stooq".to_string(),
+        change_24h: None,
+        change_percent_24h: None,
+        agreeing_sources: None,
+        stale: false,
+    })
+}
+
+async fn fetch_mock_price(symbol: &str, seed: Option<u64>) -> Result<PriceResponse, FetchError> {
+    // Generate a realistic mock price based on symbol
+    let base_price = match symbol {
+        "AAPL" => 150.0,
+        "TSLA" => 200.0,
+        "MSFT" => 300.0,
+        "GOOGL" => 2500.0,
+        "AMZN" => 3000.0,
+        "NVDA" => 400.0,
+        "META" => 250.0,
+        "NFLX" => 400.0,
+        _ => 100.0 + (symbol.len() as f64 * 10.0),
+    };
+
+    // A seed gives reproducible output for tests; without one, vary by wall
+    // clock like before so live mock data still looks alive.
+    let variation = match seed {
+        Some(seed) => seeded_variation(symbol, seed),
+        None => (chrono::Utc::now().timestamp() % 100) as f64 / 100.0 - 0.5,
+    };
+    let price = base_price * (1.0 + variation * 0.1);
+
+    Ok(PriceResponse {
+        symbol: symbol.to_string(),
+        price: (price * 100.0).round() / 100.0, // Round to 2 decimal places
+        currency: "USD".to_string(),
+        timestamp: chrono::Utc::now().timestamp(),
+        source: "mock".to_string(),
+        change_24h: Some(variation * 5.0),
+        change_percent_24h: Some(variation * 2.0),
+        agreeing_sources: None,
+        stale: false,
+    })
+}
+
+/// Deterministic replacement for the wall-clock-based variation, so the same
+/// `(symbol, seed)` always produces the same mock quote.
+fn seeded_variation(symbol: &str, seed: u64) -> f64 {
+    use std::hash::{Hash, Hasher};
+    let mut hasher = std::collections::hash_map::DefaultHasher::new();
+    symbol.hash(&mut hasher);
+    seed.hash(&mut hasher);
+    (hasher.finish() % 100) as f64 / 100.0 - 0.5
+}
+
+/// Sources queried when `source=quorum` is requested. Each is fetched
+/// concurrently and reconciled by `fetch_quorum_price`. `yahoo` and `stooq`
+/// are both key-free, so the default `min=2` quorum is reachable out of the
+/// box; `alpha_vantage` only joins in when `ALPHA_VANTAGE_API_KEY` is set.
+const QUORUM_SOURCES: &[&str] = &["yahoo", "stooq", "alpha_vantage"];
+
+const DEFAULT_MAX_DEVIATION_PCT: f64 = 0.05;
+
+/// Query several price sources concurrently and reconcile them into a single
+/// quote, discarding outliers that disagree with the group by more than
+/// `max_deviation_pct`. Requires at least `min` sources to agree, otherwise
+/// returns an error rather than trusting a single feed.
+async fn fetch_quorum_price(
+    state: &AppState,
+    symbol: &str,
+    min: usize,
+    max_deviation_pct: f64,
+) -> Result<PriceResponse, FetchError> {
+    let fetches = QUORUM_SOURCES.iter().map(|source| {
+        let source = *source;
+        async move {
+            match source {
+                "yahoo" => fetch_yahoo_price(state, symbol).await,
+                "stooq" => fetch_stooq_price(state, symbol).await,
+                _ => fetch_alpha_vantage_price(state, symbol).await,
+            }
+        }
+    });
+    let results = futures::future::join_all(fetches).await;
+
+    let successes: Vec<PriceResponse> = results
+        .into_iter()
+        .filter_map(|result| result.ok())
+        .collect();
+
+    if successes.len() < min {
+        return Err(FetchError::QuorumNotMet(format!(
+            "only {} of {} required sources responded",
+            successes.len(),
+            min
+        )));
+    }
+
+    if successes.is_empty() {
+        return Err(FetchError::QuorumNotMet("no sources responded".to_string()));
+    }
+
+    let raw_median = median(successes.iter().map(|p| p.price));
+
+    let agreeing: Vec<&PriceResponse> = successes
+        .iter()
+        .filter(|p| ((p.price - raw_median).abs() / raw_median) <= max_deviation_pct)
+        .collect();
+
+    if agreeing.len() < min {
+        return Err(FetchError::QuorumNotMet(format!(
+            "only {} of {} required sources agreed within {:.1}%",
+            agreeing.len(),
+            min,
+            max_deviation_pct * 100.0
+        )));
+    }
+
+    Ok(PriceResponse {
+        symbol: symbol.to_string(),
+        price: median(agreeing.iter().map(|p| p.price)),
+        currency: agreeing[0].currency.clone(),
+        timestamp: chrono::Utc::now().timestamp(),
+        source: "quorum".to_string(),
+        change_24h: None,
+        change_percent_24h: None,
+        agreeing_sources: Some(agreeing.iter().map(|p| p.source.clone()).collect()),
+        stale: false,
+    })
+}
+
+fn median(values: impl Iterator<Item = f64>) -> f64 {
+    let mut sorted: Vec<f64> = values.collect();
+    sorted.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    let len = sorted.len();
+    if len.is_multiple_of(2) {
+        (sorted[len / 2 - 1] + sorted[len / 2]) / 2.0
+    } else {
+        sorted[len / 2]
+    }
+}
@@ -0,0 +1,136 @@
+use std::collections::HashSet;
+use std::time::Duration;
+
+use axum::extract::ws::{Message, WebSocket, WebSocketUpgrade};
+use axum::extract::State;
+use axum::response::IntoResponse;
+use serde::Deserialize;
+use tokio::time::interval;
+use tracing::{info, warn};
+
+use crate::cache::AppState;
+use crate::{fetch_price, resolve_source, PriceResponse};
+
+/// Symbols we know how to stream prices for when a client subscribes to `"*"`.
+/// Mirrors the ticker list `fetch_mock_price` recognizes.
+const TRACKED_SYMBOLS: &[&str] = &[
+    "AAPL", "TSLA", "MSFT", "GOOGL", "AMZN", "NVDA", "META", "NFLX",
+];
+
+const POLL_INTERVAL: Duration = Duration::from_secs(5);
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+enum Subscription {
+    Symbol(String),
+    All,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(tag = "action", rename_all = "snake_case")]
+enum ControlFrame {
+    Subscribe { symbols: Vec<String> },
+    Unsubscribe { symbols: Vec<String> },
+}
+
+pub async fn stream_handler(State(state): State<AppState>, ws: WebSocketUpgrade) -> impl IntoResponse {
+    ws.on_upgrade(|socket| handle_socket(socket, state))
+}
+
+async fn handle_socket(mut socket: WebSocket, state: AppState) {
+    let mut subscriptions: HashSet<Subscription> = HashSet::new();
+    let mut last_prices: std::collections::HashMap<String, f64> = std::collections::HashMap::new();
+    let mut ticker = interval(POLL_INTERVAL);
+
+    loop {
+        tokio::select! {
+            msg = socket.recv() => {
+                match msg {
+                    Some(Ok(Message::Text(text))) => {
+                        match serde_json::from_str::<ControlFrame>(&text) {
+                            Ok(frame) => apply_control_frame(&mut subscriptions, frame),
+                            Err(e) => warn!("Ignoring malformed control frame: {}", e),
+                        }
+                    }
+                    Some(Ok(Message::Close(_))) | None => break,
+                    Some(Ok(_)) => {}
+                    Some(Err(e)) => {
+                        warn!("WebSocket error: {}", e);
+                        break;
+                    }
+                }
+            }
+            _ = ticker.tick() => {
+                for symbol in symbols_to_poll(&subscriptions) {
+                    let source = resolve_source(&symbol, "yahoo");
+                    match fetch_price(&state, &symbol, &source, "usd", None).await {
+                        Ok(price_data) => {
+                            let changed = last_prices
+                                .get(&symbol)
+                                .map(|p| *p != price_data.price)
+                                .unwrap_or(true);
+                            if changed {
+                                last_prices.insert(symbol.clone(), price_data.price);
+                                if send_price(&mut socket, &price_data).await.is_err() {
+                                    return;
+                                }
+                            }
+                        }
+                        Err(e) => warn!("stream: failed to fetch {}: {}", symbol, e),
+                    }
+                }
+            }
+        }
+    }
+
+    info!("WebSocket connection closed");
+}
+
+/// Merge newly-subscribed symbols into the connection's subscription set,
+/// normalizing duplicates and collapsing everything to `Subscription::All`
+/// once a wildcard is requested.
+fn apply_control_frame(subscriptions: &mut HashSet<Subscription>, frame: ControlFrame) {
+    match frame {
+        ControlFrame::Subscribe { symbols } => {
+            for symbol in symbols {
+                let sub = to_subscription(&symbol);
+                if sub == Subscription::All {
+                    subscriptions.clear();
+                    subscriptions.insert(Subscription::All);
+                } else if !subscriptions.contains(&Subscription::All) {
+                    subscriptions.insert(sub);
+                }
+            }
+        }
+        ControlFrame::Unsubscribe { symbols } => {
+            for symbol in symbols {
+                subscriptions.remove(&to_subscription(&symbol));
+            }
+        }
+    }
+}
+
+fn to_subscription(symbol: &str) -> Subscription {
+    if symbol.trim() == "*" {
+        Subscription::All
+    } else {
+        Subscription::Symbol(symbol.trim().to_uppercase())
+    }
+}
+
+fn symbols_to_poll(subscriptions: &HashSet<Subscription>) -> Vec<String> {
+    if subscriptions.contains(&Subscription::All) {
+        return TRACKED_SYMBOLS.iter().map(|s| s.to_string()).collect();
+    }
+    subscriptions
+        .iter()
+        .filter_map(|sub| match sub {
+            Subscription::Symbol(s) => Some(s.clone()),
+            Subscription::All => None,
+        })
+        .collect()
+}
+
+async fn send_price(socket: &mut WebSocket, price: &PriceResponse) -> Result<(), axum::Error> {
+    let payload = serde_json::to_string(price).unwrap_or_default();
+    socket.send(Message::Text(payload)).await
+}
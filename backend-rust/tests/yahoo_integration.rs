@@ -0,0 +1,178 @@
+use axum::body::Body;
+use axum::http::{Request, StatusCode};
+use backend_rust::{build_router, fetch_price, AppState, FetchError};
+use httpmock::MockServer;
+use serde_json::json;
+use tower::ServiceExt;
+
+fn chart_response(price: f64, currency: &str) -> serde_json::Value {
+    json!({
+        "chart": {
+            "result": [{
+                "meta": {
+                    "regularMarketPrice": price,
+                    "currency": currency,
+                    "regularMarketChange": 1.5,
+                    "regularMarketChangePercent": 0.8,
+                    "regularMarketTime": 1_700_000_000i64,
+                }
+            }]
+        }
+    })
+}
+
+#[tokio::test]
+async fn parses_a_valid_yahoo_quote() {
+    let server = MockServer::start();
+    let mock = server.mock(|when, then| {
+        when.method(httpmock::Method::GET)
+            .path("/v8/finance/chart/AAPL");
+        then.status(200).json_body(chart_response(187.5, "USD"));
+    });
+
+    let state = AppState::new().with_yahoo_base_url(server.base_url());
+    let price = fetch_price(&state, "AAPL", "yahoo", "usd", None)
+        .await
+        .expect("quote should parse");
+
+    mock.assert();
+    assert_eq!(price.symbol, "AAPL");
+    assert_eq!(price.price, 187.5);
+    assert_eq!(price.currency, "USD");
+    assert_eq!(price.source, "yahoo");
+}
+
+#[tokio::test]
+async fn malformed_json_is_invalid_response() {
+    let server = MockServer::start();
+    server.mock(|when, then| {
+        when.method(httpmock::Method::GET)
+            .path("/v8/finance/chart/AAPL");
+        then.status(200).json_body(json!({ "chart": { "result": [] } }));
+    });
+
+    let state = AppState::new().with_yahoo_base_url(server.base_url());
+    let err = fetch_price(&state, "AAPL", "yahoo", "usd", None)
+        .await
+        .expect_err("empty result array should fail to parse");
+
+    assert!(matches!(err, FetchError::InvalidResponse(_)));
+    assert_eq!(err.error_code(), "INVALID_RESPONSE");
+}
+
+#[tokio::test]
+async fn rate_limited_response_is_retried_then_surfaced() {
+    let server = MockServer::start();
+    let mock = server.mock(|when, then| {
+        when.method(httpmock::Method::GET)
+            .path("/v8/finance/chart/AAPL");
+        then.status(429);
+    });
+
+    let state = AppState::new().with_yahoo_base_url(server.base_url());
+    let err = fetch_price(&state, "AAPL", "yahoo", "usd", None)
+        .await
+        .expect_err("429 should surface as RateLimited after retries");
+
+    assert!(matches!(err, FetchError::RateLimited));
+    assert_eq!(err.error_code(), "RATE_LIMITED");
+    // 429 is retryable, so the mock should have been hit more than once.
+    assert!(mock.hits() > 1);
+}
+
+#[tokio::test]
+async fn not_found_is_not_retried() {
+    let server = MockServer::start();
+    let mock = server.mock(|when, then| {
+        when.method(httpmock::Method::GET)
+            .path("/v8/finance/chart/NOTASYMBOL");
+        then.status(404);
+    });
+
+    let state = AppState::new().with_yahoo_base_url(server.base_url());
+    let err = fetch_price(&state, "NOTASYMBOL", "yahoo", "usd", None)
+        .await
+        .expect_err("404 should not be retried");
+
+    assert!(matches!(err, FetchError::StatusCode(404)));
+    assert_eq!(err.error_code(), "SYMBOL_NOT_FOUND");
+    // 404 is not retryable, so the mock should only be hit once.
+    assert_eq!(mock.hits(), 1);
+}
+
+#[tokio::test]
+async fn alpha_vantage_quote_uses_the_injected_base_url() {
+    let server = MockServer::start();
+    let mock = server.mock(|when, then| {
+        when.method(httpmock::Method::GET)
+            .path("/query")
+            .query_param("function", "GLOBAL_QUOTE")
+            .query_param("symbol", "AAPL");
+        then.status(200).json_body(json!({
+            "Global Quote": {
+                "05. price": "187.50",
+                "09. change": "1.50",
+                "10. change percent": "0.8000%",
+            }
+        }));
+    });
+
+    std::env::set_var("ALPHA_VANTAGE_API_KEY", "test-key");
+    let state = AppState::new().with_alpha_vantage_base_url(server.base_url());
+    let result = fetch_price(&state, "AAPL", "alpha_vantage", "usd", None).await;
+    std::env::remove_var("ALPHA_VANTAGE_API_KEY");
+
+    let price = result.expect("quote should parse");
+    mock.assert();
+    assert_eq!(price.price, 187.50);
+    assert_eq!(price.source, "alpha_vantage");
+}
+
+#[tokio::test]
+async fn mock_source_is_deterministic_under_a_seed() {
+    let state = AppState::new();
+    let first = fetch_price(&state, "AAPL", "mock", "usd", Some(42))
+        .await
+        .unwrap();
+    let second = fetch_price(&state, "AAPL", "mock", "usd", Some(42))
+        .await
+        .unwrap();
+
+    assert_eq!(first.price, second.price);
+    assert_eq!(first.change_24h, second.change_24h);
+}
+
+#[tokio::test]
+async fn quorum_with_min_zero_does_not_panic_when_all_sources_fail() {
+    let yahoo = MockServer::start();
+    yahoo.mock(|when, then| {
+        when.method(httpmock::Method::GET);
+        then.status(404);
+    });
+    let stooq = MockServer::start();
+    stooq.mock(|when, then| {
+        when.method(httpmock::Method::GET);
+        then.status(404);
+    });
+
+    std::env::remove_var("ALPHA_VANTAGE_API_KEY");
+    let state = AppState::new()
+        .with_yahoo_base_url(yahoo.base_url())
+        .with_stooq_base_url(stooq.base_url());
+    let app = build_router(state);
+
+    // Previously `min=0` reached `median()` on an empty vector and panicked
+    // (subtract-overflow); it should instead come back as an ordinary
+    // QUORUM_NOT_MET error response.
+    let response = app
+        .oneshot(
+            Request::builder()
+                .uri("/price/AAPL?source=quorum&min=0")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
+}